@@ -1,7 +1,71 @@
 use crate::io::IO;
-use crate::task_manager::{TaskManager, TaskPriority, TaskStatus};
+use crate::task_manager::{split_hours_minutes, TaskManager, TaskPriority, TaskStatus};
+use clap::{Parser, Subcommand};
 use std::io;
 
+/// Parsed command-line invocation. With no subcommand the interactive menu is
+/// started (or the TUI, when `--tui` is given); otherwise the matching verb is
+/// dispatched against the manager.
+#[derive(Parser)]
+#[command(name = "task_manager", about = "A simple CLI task manager")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Launch the full-screen TUI instead of the interactive text menu.
+    #[arg(long)]
+    pub tui: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Add a new task.
+    Add {
+        #[arg(long)]
+        title: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        #[arg(long, default_value = "low")]
+        priority: String,
+    },
+    /// List tasks, optionally filtered by status and/or priority.
+    List {
+        #[arg(long)]
+        status: Option<String>,
+        #[arg(long)]
+        priority: Option<String>,
+    },
+    /// Change a task's status.
+    Status { id: u32, new_status: String },
+    /// Delete a task.
+    Delete { id: u32 },
+    /// Mark a task as done.
+    Done { id: u32 },
+    /// Make `id` depend on `on` (i.e. `on` must be done first).
+    Depend { id: u32, on: u32 },
+    /// Remove the dependency of `id` on `on`.
+    Undepend { id: u32, on: u32 },
+    /// List tasks whose dependencies are all done.
+    Ready,
+}
+
+fn parse_priority(value: &str) -> Option<TaskPriority> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "low" => Some(TaskPriority::Low),
+        "2" | "medium" => Some(TaskPriority::Medium),
+        "3" | "high" => Some(TaskPriority::High),
+        _ => None,
+    }
+}
+
+fn parse_status(value: &str) -> Option<TaskStatus> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "todo" => Some(TaskStatus::Todo),
+        "2" | "inprogress" | "in-progress" => Some(TaskStatus::InProgress),
+        "3" | "done" => Some(TaskStatus::Done),
+        _ => None,
+    }
+}
+
 pub struct TaskApp {
     task_manager: TaskManager,
     io: Box<dyn IO>,
@@ -12,6 +76,76 @@ impl TaskApp {
         Self { task_manager, io }
     }
 
+    /// Entry point honouring a parsed [`Cli`]: dispatch a subcommand if one was
+    /// given, otherwise fall back to the interactive menu.
+    pub fn run_with_args(&mut self, cli: Cli) -> io::Result<()> {
+        match cli.command {
+            Some(command) => self.dispatch(command),
+            None => self.run(),
+        }
+    }
+
+    fn dispatch(&mut self, command: Command) -> io::Result<()> {
+        match command {
+            Command::Add {
+                title,
+                description,
+                priority,
+            } => {
+                let priority = parse_priority(&priority).unwrap_or(TaskPriority::Low);
+                match self.add_task_logic(title, description, priority) {
+                    Ok(id) => self.io.write_line(&format!("Task added with ID:{}", id)),
+                    Err(err) => self.io.write_line(&err),
+                }
+            }
+            Command::List { status, priority } => {
+                let status = status.as_deref().and_then(parse_status);
+                let priority = priority.as_deref().and_then(parse_priority);
+                self.list_tasks(status, priority)
+            }
+            Command::Status { id, new_status } => match parse_status(&new_status) {
+                Some(status) => {
+                    if self.task_manager.update_status(id, status)? {
+                        self.io.write_line("Task updated successfully!")
+                    } else {
+                        self.io.write_line("Task not found!")
+                    }
+                }
+                None => self.io.write_line("Invalid status!"),
+            },
+            Command::Delete { id } => {
+                if self.task_manager.delete_task(id)? {
+                    self.io.write_line("Task deleted successfully!")
+                } else {
+                    self.io.write_line("Task not found!")
+                }
+            }
+            Command::Done { id } => {
+                if self.task_manager.update_status(id, TaskStatus::Done)? {
+                    self.io.write_line("Task marked as done!")
+                } else {
+                    self.io.write_line("Task not found!")
+                }
+            }
+            Command::Depend { id, on } => {
+                if self.task_manager.add_dependency(id, on)? {
+                    self.io.write_line("Dependency added!")
+                } else {
+                    self.io
+                        .write_line("Could not add dependency (missing task or would create a cycle).")
+                }
+            }
+            Command::Undepend { id, on } => {
+                if self.task_manager.remove_dependency(id, on)? {
+                    self.io.write_line("Dependency removed!")
+                } else {
+                    self.io.write_line("Dependency not found!")
+                }
+            }
+            Command::Ready => self.list_ready(),
+        }
+    }
+
     pub fn run(&mut self) -> io::Result<()> {
         loop {
             self.io.write_line("\nTask Manager")?;
@@ -19,14 +153,23 @@ impl TaskApp {
             self.io.write_line("2. List Tasks")?;
             self.io.write_line("3. Update Task Status")?;
             self.io.write_line("4. Delete Task")?;
-            self.io.write_line("5. Exit")?;
+            self.io.write_line("5. Archive Completed")?;
+            self.io.write_line("6. Exit")?;
+
+            let (choice, count) = self.io.read_line_counted()?;
+            // A count of 0 means the input stream is exhausted (closed pipe or
+            // EOF); stop instead of looping forever on an empty read.
+            if count == 0 {
+                break;
+            }
 
-            match self.io.read_line()?.as_str() {
+            match choice.as_str() {
                 "1" => self.handle_add_task()?,
                 "2" => self.handle_list_tasks()?,
                 "3" => self.handle_update_status()?,
                 "4" => self.handle_delete_task()?,
-                "5" => break,
+                "5" => self.handle_archive_done()?,
+                "6" => break,
                 _ => self.io.write_line("Invalid choice!")?,
             }
         }
@@ -49,8 +192,17 @@ impl TaskApp {
             _ => TaskPriority::Low,
         };
 
+        self.io
+            .write_line("Enter tags (comma-separated, optional):")?;
+        let tags_line = self.io.read_line()?;
+
         match self.add_task_logic(title, description, priority) {
-            Ok(id) => self.io.write_line(&format!("Task added with ID:{}", id)),
+            Ok(id) => {
+                for tag in tags_line.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                    self.task_manager.add_tag(id, tag)?;
+                }
+                self.io.write_line(&format!("Task added with ID:{}", id))
+            }
             Err(err) => self.io.write_line(&err),
         }
     }
@@ -84,20 +236,69 @@ impl TaskApp {
     }
 
     fn handle_list_tasks(&mut self) -> io::Result<()> {
-        for task in self.task_manager.list_tasks_sorted_by_priority() {
-            self.io.write_line(&format!("\nID: {}", task.id))?;
-            self.io.write_line(&format!("Title: {}", task.title))?;
-            self.io
-                .write_line(&format!("Description: {}", task.description))?;
-            self.io.write_line(&format!("Status: {:?}", task.status))?;
-            self.io
-                .write_line(&format!("Priority: {:?}", task.priority))?;
-            self.io
-                .write_line(&format!("Created: {}", task.created_at))?;
+        self.list_tasks(None, None)
+    }
+
+    fn list_tasks(
+        &mut self,
+        status: Option<TaskStatus>,
+        priority: Option<TaskPriority>,
+    ) -> io::Result<()> {
+        let lines: Vec<String> = self
+            .task_manager
+            .list_tasks_sorted_by_priority()
+            .iter()
+            .filter(|task| status.is_none_or(|s| task.status == s))
+            .filter(|task| priority.is_none_or(|p| task.priority == p))
+            .flat_map(|task| {
+                let (hours, minutes) = split_hours_minutes(self.task_manager.total_logged(task.id));
+                let mut tags: Vec<&str> = task.tags.iter().map(String::as_str).collect();
+                tags.sort_unstable();
+                vec![
+                    format!("\nID: {}", task.id),
+                    format!("Title: {}", task.title),
+                    format!("Description: {}", task.description),
+                    format!("Status: {:?}", task.status),
+                    format!("Priority: {:?}", task.priority),
+                    format!("Created: {}", task.created_at),
+                    format!("Logged: {}h {}m", hours, minutes),
+                    format!("Tags: {}", tags.join(", ")),
+                ]
+            })
+            .collect();
+
+        for line in lines {
+            self.io.write_line(&line)?;
+        }
+        Ok(())
+    }
+
+    fn list_ready(&mut self) -> io::Result<()> {
+        let lines: Vec<String> = self
+            .task_manager
+            .list_ready_tasks()
+            .iter()
+            .flat_map(|task| {
+                vec![
+                    format!("\nID: {}", task.id),
+                    format!("Title: {}", task.title),
+                    format!("Priority: {:?}", task.priority),
+                ]
+            })
+            .collect();
+
+        for line in lines {
+            self.io.write_line(&line)?;
         }
         Ok(())
     }
 
+    fn handle_archive_done(&mut self) -> io::Result<()> {
+        let archived = self.task_manager.archive_done()?;
+        self.io
+            .write_line(&format!("Archived {} completed task(s).", archived))
+    }
+
     fn handle_update_status(&mut self) -> io::Result<()> {
         self.io.write_line("Enter task ID:")?;
         let id_str = self.io.read_line()?;
@@ -1,9 +1,23 @@
 // Module: io
 use std::any::Any;
-use std::io;
+use std::io::{self, BufRead, Read, Write};
 
 pub trait IO {
     fn read_line(&mut self) -> io::Result<String>;
+
+    /// Read a line and report the number of bytes read *before* trimming, like
+    /// `std`/`tokio`'s `read_line`. A count of `0` unambiguously signals EOF,
+    /// keeping the blank-line case (`"\n"`, count 1) distinct from end of
+    /// stream so a command loop can stop instead of spinning on a closed pipe.
+    ///
+    /// Required rather than defaulted: a default delegating to [`read_line`]
+    /// could only report the trimmed length, which would collapse the blank
+    /// line and EOF cases back together and reintroduce the ambiguity this
+    /// contract removes.
+    ///
+    /// [`read_line`]: IO::read_line
+    fn read_line_counted(&mut self) -> io::Result<(String, usize)>;
+
     fn write_line(&mut self, line: &str) -> io::Result<()>;
     fn as_any(&self) -> &dyn Any;
 }
@@ -12,9 +26,13 @@ pub struct ConsoleIO;
 
 impl IO for ConsoleIO {
     fn read_line(&mut self) -> io::Result<String> {
+        Ok(self.read_line_counted()?.0)
+    }
+
+    fn read_line_counted(&mut self) -> io::Result<(String, usize)> {
         let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        Ok(input.trim().to_string())
+        let count = io::stdin().read_line(&mut input)?;
+        Ok((input.trim().to_string(), count))
     }
 
     fn write_line(&mut self, line: &str) -> io::Result<()> {
@@ -27,36 +45,247 @@ impl IO for ConsoleIO {
     }
 }
 
-#[cfg(test)]
-mod tests_io {
-    use super::*;
-    use std::io::{self};
+/// [`IO`] over any buffered reader and any writer, so the task manager can be
+/// driven from a file of commands, a Unix pipe, or a socket instead of stdin.
+/// `read_line` keeps the same trimming behaviour as [`ConsoleIO`].
+pub struct StreamIO<R: BufRead, W: Write> {
+    reader: R,
+    writer: W,
+    buf: String,
+}
 
-    struct MockIO {
-        input: Vec<String>,
-        output: Vec<String>,
+impl<R: BufRead, W: Write> StreamIO<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            buf: String::new(),
+        }
     }
 
-    impl MockIO {
-        fn new(input: Vec<String>) -> Self {
+    // The core IO is exposed as inherent methods so borrowed writers (a
+    // `&mut Vec<u8>`, a socket) work without the `'static` bound that the `IO`
+    // trait's `as_any` forces. The trait impl below simply delegates.
+    pub fn read_line(&mut self) -> io::Result<String> {
+        Ok(self.read_line_counted()?.0)
+    }
+
+    pub fn read_line_counted(&mut self) -> io::Result<(String, usize)> {
+        self.buf.clear();
+        let count = self.reader.read_line(&mut self.buf)?;
+        Ok((self.buf.trim().to_string(), count))
+    }
+
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()
+    }
+}
+
+impl<R: BufRead + 'static, W: Write + 'static> IO for StreamIO<R, W> {
+    fn read_line(&mut self) -> io::Result<String> {
+        StreamIO::read_line(self)
+    }
+
+    fn read_line_counted(&mut self) -> io::Result<(String, usize)> {
+        StreamIO::read_line_counted(self)
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        StreamIO::write_line(self, line)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(feature = "async")]
+pub use async_io::{AsyncConsoleIO, AsyncIO};
+
+/// Async counterpart to [`IO`], gated behind the `async` feature so the
+/// synchronous path stays dependency-free. Lets the task manager await user
+/// input while concurrently servicing a timer, watcher, or socket.
+#[cfg(feature = "async")]
+mod async_io {
+    use std::io;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Stdin, Stdout};
+
+    #[allow(async_fn_in_trait)]
+    pub trait AsyncIO {
+        /// Read a line, returning `None` at end of stream (0 bytes read).
+        async fn read_line(&mut self) -> io::Result<Option<String>>;
+        async fn write_line(&mut self, line: &str) -> io::Result<()>;
+    }
+
+    pub struct AsyncConsoleIO {
+        reader: BufReader<Stdin>,
+        writer: Stdout,
+    }
+
+    impl AsyncConsoleIO {
+        pub fn new() -> Self {
             Self {
-                input,
-                output: Vec::new(),
+                reader: BufReader::new(tokio::io::stdin()),
+                writer: tokio::io::stdout(),
             }
         }
     }
 
+    impl Default for AsyncConsoleIO {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl AsyncIO for AsyncConsoleIO {
+        async fn read_line(&mut self) -> io::Result<Option<String>> {
+            let mut input = String::new();
+            let count = self.reader.read_line(&mut input).await?;
+            if count == 0 {
+                Ok(None)
+            } else {
+                Ok(Some(input.trim().to_string()))
+            }
+        }
+
+        async fn write_line(&mut self, line: &str) -> io::Result<()> {
+            self.writer.write_all(line.as_bytes()).await?;
+            self.writer.write_all(b"\n").await?;
+            self.writer.flush().await
+        }
+    }
+}
+
+/// Length-delimited frame codec over any reader/writer, for an IPC/daemon mode
+/// where embedded newlines in task descriptions would corrupt a line-based
+/// protocol. Each frame is a big-endian `u32` length prefix followed by exactly
+/// that many payload bytes, mirroring tokio's `length_delimited` framing.
+pub struct FramedIO<R: Read, W: Write> {
+    reader: R,
+    writer: W,
+    max_frame_size: usize,
+}
+
+impl<R: Read, W: Write> FramedIO<R, W> {
+    /// Default guard against absurd length prefixes (8 MiB).
+    pub const DEFAULT_MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+    pub fn new(reader: R, writer: W) -> Self {
+        Self::with_max_frame_size(reader, writer, Self::DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    pub fn with_max_frame_size(reader: R, writer: W, max_frame_size: usize) -> Self {
+        Self {
+            reader,
+            writer,
+            max_frame_size,
+        }
+    }
+
+    /// Read one frame: a big-endian `u32` length prefix followed by exactly that
+    /// many payload bytes. `read_exact` loops until the full length is
+    /// satisfied, and a length over the configured maximum is rejected before
+    /// any payload allocation.
+    pub fn read_frame(&mut self) -> io::Result<Vec<u8>> {
+        let mut len_buf = [0u8; 4];
+        self.reader.read_exact(&mut len_buf)?;
+        let len = u32::from_be_bytes(len_buf) as usize;
+        if len > self.max_frame_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame length {} exceeds maximum {}", len, self.max_frame_size),
+            ));
+        }
+
+        let mut payload = vec![0u8; len];
+        self.reader.read_exact(&mut payload)?;
+        Ok(payload)
+    }
+
+    /// Write `payload` as a single length-delimited frame.
+    pub fn write_frame(&mut self, payload: &[u8]) -> io::Result<()> {
+        if payload.len() > self.max_frame_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "frame length {} exceeds maximum {}",
+                    payload.len(),
+                    self.max_frame_size
+                ),
+            ));
+        }
+
+        self.writer.write_all(&(payload.len() as u32).to_be_bytes())?;
+        self.writer.write_all(payload)?;
+        self.writer.flush()
+    }
+}
+
+/// Scripted [`IO`] for tests and downstream integration harnesses, modelled on
+/// tokio-test's `io::Builder`: reads are replayed in FIFO order and writes are
+/// checked against expectations in order. [`MockIO::assert_done`] fails if any
+/// scripted read was left unconsumed or an expected write never occurred.
+pub mod mock {
+    use super::IO;
+    use std::any::Any;
+    use std::collections::VecDeque;
+    use std::io;
+
+    pub struct MockIO {
+        reads: VecDeque<String>,
+        expected_writes: VecDeque<String>,
+        writes: Vec<String>,
+    }
+
+    impl MockIO {
+        pub fn builder() -> Builder {
+            Builder::default()
+        }
+
+        /// The full sequence of lines written, in order.
+        pub fn writes(&self) -> &[String] {
+            &self.writes
+        }
+
+        /// Panic if any scripted read or expected write was not consumed.
+        pub fn assert_done(&self) {
+            assert!(
+                self.reads.is_empty(),
+                "{} scripted read(s) left unconsumed",
+                self.reads.len()
+            );
+            assert!(
+                self.expected_writes.is_empty(),
+                "{} expected write(s) never occurred",
+                self.expected_writes.len()
+            );
+        }
+    }
+
     impl IO for MockIO {
         fn read_line(&mut self) -> io::Result<String> {
-            if let Some(line) = self.input.pop() {
-                Ok(line)
-            } else {
-                Ok(String::new())
+            Ok(self.read_line_counted()?.0)
+        }
+
+        fn read_line_counted(&mut self) -> io::Result<(String, usize)> {
+            match self.reads.pop_front() {
+                // `+ 1` stands in for the stripped newline, so even a scripted
+                // blank line reports a non-zero count; an exhausted script
+                // reports `0` to signal EOF.
+                Some(line) => {
+                    let count = line.len() + 1;
+                    Ok((line, count))
+                }
+                None => Ok((String::new(), 0)),
             }
         }
 
         fn write_line(&mut self, line: &str) -> io::Result<()> {
-            self.output.push(line.to_string());
+            if let Some(expected) = self.expected_writes.pop_front() {
+                assert_eq!(line, expected, "unexpected write");
+            }
+            self.writes.push(line.to_string());
             Ok(())
         }
 
@@ -65,49 +294,153 @@ mod tests_io {
         }
     }
 
+    /// Ordered script for a [`MockIO`]. Chain `read`/`write` then `build`.
+    #[derive(Default)]
+    pub struct Builder {
+        reads: VecDeque<String>,
+        expected_writes: VecDeque<String>,
+    }
+
+    impl Builder {
+        pub fn read(&mut self, line: &str) -> &mut Self {
+            self.reads.push_back(line.to_string());
+            self
+        }
+
+        pub fn write(&mut self, line: &str) -> &mut Self {
+            self.expected_writes.push_back(line.to_string());
+            self
+        }
+
+        pub fn build(&mut self) -> MockIO {
+            MockIO {
+                reads: std::mem::take(&mut self.reads),
+                expected_writes: std::mem::take(&mut self.expected_writes),
+                writes: Vec::new(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_io {
+    use super::mock::MockIO;
+    use super::*;
+
     #[test]
     fn read_line_returns_correct_string() {
-        let mut mock_io = MockIO::new(vec!["Hello, world!".to_string()]);
+        let mut mock_io = MockIO::builder().read("Hello, world!").build();
         let result = mock_io.read_line().unwrap();
         assert_eq!(result, "Hello, world!");
     }
 
     #[test]
     fn read_line_handles_empty_input() {
-        let mut mock_io = MockIO::new(vec!["".to_string()]);
+        let mut mock_io = MockIO::builder().read("").build();
         let result = mock_io.read_line().unwrap();
         assert_eq!(result, "");
     }
 
     #[test]
     fn read_line_returns_empty_string_when_no_input() {
-        let mut mock_io = MockIO::new(vec![]);
+        let mut mock_io = MockIO::builder().build();
         let result = mock_io.read_line().unwrap();
         assert_eq!(result, "");
     }
 
     #[test]
-    fn read_line_handles_multiple_inputs() {
-        let mut mock_io = MockIO::new(vec!["First".to_string(), "Second".to_string()]);
+    fn read_line_preserves_fifo_order() {
+        let mut mock_io = MockIO::builder().read("First").read("Second").build();
         let result1 = mock_io.read_line().unwrap();
         let result2 = mock_io.read_line().unwrap();
-        assert_eq!(result1, "Second");
-        assert_eq!(result2, "First");
+        assert_eq!(result1, "First");
+        assert_eq!(result2, "Second");
     }
 
     #[test]
     fn write_line_outputs_correct_string() {
-        let mut mock_io = MockIO::new(vec![]);
+        let mut mock_io = MockIO::builder().build();
         let line = "Hello, world!";
         mock_io.write_line(line).unwrap();
-        assert_eq!(mock_io.output, vec![line.to_string()]);
+        assert_eq!(mock_io.writes(), &["Hello, world!".to_string()]);
     }
 
     #[test]
     fn write_line_handles_empty_string() {
-        let mut mock_io = MockIO::new(vec![]);
+        let mut mock_io = MockIO::builder().build();
         let line = "";
         mock_io.write_line(line).unwrap();
-        assert_eq!(mock_io.output, vec![line.to_string()]);
+        assert_eq!(mock_io.writes(), &["".to_string()]);
+    }
+
+    #[test]
+    fn assert_done_passes_when_script_fully_consumed() {
+        let mut mock_io = MockIO::builder().read("in").write("out").build();
+        assert_eq!(mock_io.read_line().unwrap(), "in");
+        mock_io.write_line("out").unwrap();
+        mock_io.assert_done();
+    }
+
+    #[test]
+    #[should_panic(expected = "unconsumed")]
+    fn assert_done_fails_on_unconsumed_read() {
+        let mock_io = MockIO::builder().read("never read").build();
+        mock_io.assert_done();
+    }
+
+    #[test]
+    fn stream_io_reads_trimmed_lines() {
+        let input = b"first\nsecond\n";
+        let mut stream_io = StreamIO::new(&input[..], Vec::new());
+        assert_eq!(stream_io.read_line().unwrap(), "first");
+        assert_eq!(stream_io.read_line().unwrap(), "second");
+    }
+
+    #[test]
+    fn stream_io_counts_eof_distinctly_from_blank_line() {
+        let input = b"\n";
+        let mut stream_io = StreamIO::new(&input[..], Vec::new());
+
+        // Blank line: one byte read, empty after trimming.
+        let (line, count) = stream_io.read_line_counted().unwrap();
+        assert_eq!(line, "");
+        assert_eq!(count, 1);
+
+        // EOF: zero bytes read.
+        let (line, count) = stream_io.read_line_counted().unwrap();
+        assert_eq!(line, "");
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn framed_io_round_trips_a_frame() {
+        let mut buffer: Vec<u8> = Vec::new();
+        {
+            let mut framed = FramedIO::new(&[][..], &mut buffer);
+            framed.write_frame(b"hello\nworld").unwrap();
+        }
+
+        let mut framed = FramedIO::new(&buffer[..], Vec::new());
+        assert_eq!(framed.read_frame().unwrap(), b"hello\nworld");
+    }
+
+    #[test]
+    fn framed_io_rejects_oversized_length_prefix() {
+        // Length prefix of 16 with a 4-byte cap must be rejected before reading.
+        let input = [0u8, 0, 0, 16];
+        let mut framed = FramedIO::with_max_frame_size(&input[..], Vec::new(), 4);
+        let err = framed.read_frame().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn stream_io_writes_lines_to_writer() {
+        let input: &[u8] = b"";
+        let mut output: Vec<u8> = Vec::new();
+        {
+            let mut stream_io = StreamIO::new(input, &mut output);
+            stream_io.write_line("hello").unwrap();
+        }
+        assert_eq!(output, b"hello\n");
     }
 }
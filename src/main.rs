@@ -1,5 +1,8 @@
+use clap::Parser;
 use std::error::Error;
 use std::io;
+use task_manager::app::{Cli, TaskApp};
+use task_manager::io::ConsoleIO;
 use task_manager::task_manager::TaskManager;
 use task_manager::tui_app;
 use tui_app::TuiApp;
@@ -22,7 +25,14 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 
 fn run() -> io::Result<()> {
+    let cli = Cli::parse();
     let task_manager = TaskManager::new("tasks.json")?;
-    let mut app = TuiApp::new(task_manager);
-    app.run()
+
+    if cli.command.is_none() && cli.tui {
+        let mut app = TuiApp::new(task_manager);
+        app.run()
+    } else {
+        let mut app = TaskApp::new(task_manager, Box::new(ConsoleIO));
+        app.run_with_args(cli)
+    }
 }
@@ -1,10 +1,11 @@
 use chrono::Local;
 use serde_derive::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::{File, OpenOptions};
 use std::io::{self, BufReader, Write};
 use std::path::Path;
+use std::time::Instant;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 pub enum TaskStatus {
@@ -32,6 +33,17 @@ impl PartialOrd for TaskPriority {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct TimeEntry {
+    pub(crate) date: String,
+    pub(crate) minutes: u32,
+}
+
+/// Split a minute count into whole hours and leftover minutes for display.
+pub fn split_hours_minutes(minutes: u32) -> (u32, u32) {
+    (minutes / 60, minutes % 60)
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Task {
     pub(crate) id: u32,
@@ -40,12 +52,21 @@ pub struct Task {
     pub(crate) status: TaskStatus,
     pub(crate) created_at: String,
     pub(crate) priority: TaskPriority,
+    #[serde(default)]
+    pub(crate) dependencies: HashSet<u32>,
+    #[serde(default)]
+    pub(crate) time_entries: Vec<TimeEntry>,
+    #[serde(default)]
+    pub(crate) tags: HashSet<String>,
+    #[serde(default)]
+    pub(crate) order: u32,
 }
 
 pub struct TaskManager {
     tasks: HashMap<u32, Task>,
     next_id: u32,
     file_path: String,
+    active_timer: Option<(u32, Instant)>,
 }
 
 impl TaskManager {
@@ -54,6 +75,7 @@ impl TaskManager {
             tasks: HashMap::new(),
             next_id: 1,
             file_path: file_path.to_string(),
+            active_timer: None,
         };
 
         if Path::new(file_path).exists() {
@@ -69,6 +91,12 @@ impl TaskManager {
         description: String,
         priority: TaskPriority,
     ) -> io::Result<u32> {
+        let order = self
+            .tasks
+            .values()
+            .map(|task| task.order)
+            .max()
+            .map_or(0, |max| max + 1);
         let task = Task {
             id: self.next_id,
             title,
@@ -76,6 +104,10 @@ impl TaskManager {
             status: TaskStatus::Todo,
             created_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             priority,
+            dependencies: HashSet::new(),
+            time_entries: Vec::new(),
+            tags: HashSet::new(),
+            order,
         };
 
         self.tasks.insert(task.id, task);
@@ -109,6 +141,221 @@ impl TaskManager {
         tasks
     }
 
+    /// Record that `id` cannot start until `depends_on` is `Done`.
+    ///
+    /// The edge is rejected (returning `Ok(false)`) when either task is
+    /// missing, when the two ids are equal, or when adding it would introduce
+    /// a cycle. Cycles are detected by inserting the edge tentatively and
+    /// running a Kahn topological sort over the resulting graph.
+    pub fn add_dependency(&mut self, id: u32, depends_on: u32) -> io::Result<bool> {
+        if id == depends_on
+            || !self.tasks.contains_key(&id)
+            || !self.tasks.contains_key(&depends_on)
+        {
+            return Ok(false);
+        }
+
+        if !self.tasks.get_mut(&id).unwrap().dependencies.insert(depends_on) {
+            return Ok(true);
+        }
+
+        if self.has_cycle() {
+            self.tasks.get_mut(&id).unwrap().dependencies.remove(&depends_on);
+            return Ok(false);
+        }
+
+        self.save_tasks()?;
+        Ok(true)
+    }
+
+    pub fn remove_dependency(&mut self, id: u32, depends_on: u32) -> io::Result<bool> {
+        if let Some(task) = self.tasks.get_mut(&id) {
+            if task.dependencies.remove(&depends_on) {
+                self.save_tasks()?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Return the tasks that are not yet `Done` and whose dependencies are all
+    /// `Done` — the zero-in-degree layer of the dependency DAG — sorted by
+    /// priority.
+    pub fn list_ready_tasks(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .tasks
+            .values()
+            .filter(|task| task.status != TaskStatus::Done)
+            .filter(|task| {
+                task.dependencies.iter().all(|dep| {
+                    self.tasks
+                        .get(dep)
+                        .is_none_or(|d| d.status == TaskStatus::Done)
+                })
+            })
+            .collect();
+        tasks.sort_by_key(|task| task.priority);
+        tasks
+    }
+
+    /// Move `id` so it sits immediately before `target` in the custom order.
+    pub fn move_before(&mut self, id: u32, target: u32) -> io::Result<bool> {
+        self.reposition(id, target, false)
+    }
+
+    /// Move `id` so it sits immediately after `target` in the custom order.
+    pub fn move_after(&mut self, id: u32, target: u32) -> io::Result<bool> {
+        self.reposition(id, target, true)
+    }
+
+    /// Renumber the `order` keys so `id` lands adjacent to `target`. The whole
+    /// sequence is compacted to `0..n` afterwards so keys stay dense.
+    fn reposition(&mut self, id: u32, target: u32, after: bool) -> io::Result<bool> {
+        if id == target || !self.tasks.contains_key(&id) || !self.tasks.contains_key(&target) {
+            return Ok(false);
+        }
+
+        let mut ordered: Vec<u32> = self.list_tasks_ordered().iter().map(|t| t.id).collect();
+        ordered.retain(|&other| other != id);
+        let pos = ordered.iter().position(|&other| other == target).unwrap();
+        ordered.insert(if after { pos + 1 } else { pos }, id);
+
+        for (index, task_id) in ordered.iter().enumerate() {
+            self.tasks.get_mut(task_id).unwrap().order = index as u32;
+        }
+        self.save_tasks()?;
+        Ok(true)
+    }
+
+    /// Tasks in the hand-curated custom order (ties broken by id).
+    pub fn list_tasks_ordered(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self.tasks.values().collect();
+        tasks.sort_by(|a, b| a.order.cmp(&b.order).then(a.id.cmp(&b.id)));
+        tasks
+    }
+
+    pub fn add_tag(&mut self, id: u32, tag: &str) -> io::Result<bool> {
+        if let Some(task) = self.tasks.get_mut(&id) {
+            if task.tags.insert(tag.to_string()) {
+                self.save_tasks()?;
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    pub fn remove_tag(&mut self, id: u32, tag: &str) -> io::Result<bool> {
+        if let Some(task) = self.tasks.get_mut(&id) {
+            if task.tags.remove(tag) {
+                self.save_tasks()?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Tasks carrying `tag`, sorted by priority.
+    pub fn list_tasks_with_tag(&self, tag: &str) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .tasks
+            .values()
+            .filter(|task| task.tags.contains(tag))
+            .collect();
+        tasks.sort_by_key(|task| task.priority);
+        tasks
+    }
+
+    /// All distinct tags in use, sorted — handy for cycling a filter.
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .tasks
+            .values()
+            .flat_map(|task| task.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Start the wall-clock timer for `id`, moving the task to `InProgress`.
+    ///
+    /// Any timer already running is discarded in favour of the new one. Returns
+    /// `Ok(false)` if the task does not exist.
+    pub fn start_timer(&mut self, id: u32) -> io::Result<bool> {
+        if !self.tasks.contains_key(&id) {
+            return Ok(false);
+        }
+        self.active_timer = Some((id, Instant::now()));
+        self.update_status(id, TaskStatus::InProgress)?;
+        Ok(true)
+    }
+
+    /// Stop the active timer if it belongs to `id`, appending a `TimeEntry`
+    /// dated with the current local day for the elapsed whole minutes.
+    pub fn stop_timer(&mut self, id: u32) -> io::Result<bool> {
+        let minutes = match self.active_timer {
+            Some((timed_id, start)) if timed_id == id => {
+                (start.elapsed().as_secs() / 60) as u32
+            }
+            _ => return Ok(false),
+        };
+
+        self.active_timer = None;
+        if let Some(task) = self.tasks.get_mut(&id) {
+            task.time_entries.push(TimeEntry {
+                date: Local::now().format("%Y-%m-%d").to_string(),
+                minutes,
+            });
+            self.save_tasks()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Total minutes logged against `id` across all sessions.
+    pub fn total_logged(&self, id: u32) -> u32 {
+        self.tasks
+            .get(&id)
+            .map(|task| task.time_entries.iter().map(|entry| entry.minutes).sum())
+            .unwrap_or(0)
+    }
+
+    /// Run a Kahn topological sort over the dependency graph, returning `true`
+    /// if not every node could be visited (i.e. a cycle exists). In-degree is
+    /// the number of a task's dependencies that still refer to a live task.
+    fn has_cycle(&self) -> bool {
+        let mut in_degree: HashMap<u32, usize> =
+            self.tasks.keys().map(|id| (*id, 0usize)).collect();
+        for task in self.tasks.values() {
+            for dep in &task.dependencies {
+                if self.tasks.contains_key(dep) {
+                    *in_degree.get_mut(&task.id).unwrap() += 1;
+                }
+            }
+        }
+
+        let mut queue: VecDeque<u32> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut visited = 0;
+        while let Some(node) = queue.pop_front() {
+            visited += 1;
+            for task in self.tasks.values() {
+                if task.dependencies.contains(&node) {
+                    let degree = in_degree.get_mut(&task.id).unwrap();
+                    *degree -= 1;
+                    if *degree == 0 {
+                        queue.push_back(task.id);
+                    }
+                }
+            }
+        }
+
+        visited < self.tasks.len()
+    }
+
     fn save_tasks(&self) -> io::Result<()> {
         let json = serde_json::to_string_pretty(&self.tasks)?;
         let mut file = OpenOptions::new()
@@ -121,6 +368,85 @@ impl TaskManager {
         Ok(())
     }
 
+    /// Path of the JSON file backing this manager.
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    /// Companion archive file sitting alongside [`file_path`](Self::file_path).
+    fn archive_path(&self) -> String {
+        match self.file_path.strip_suffix(".json") {
+            Some(stem) => format!("{}.archive.json", stem),
+            None => format!("{}.archive.json", self.file_path),
+        }
+    }
+
+    /// Move every `Done` task out of the live set and append it to the archive
+    /// file, keeping the active working set small. Returns the number moved.
+    pub fn archive_done(&mut self) -> io::Result<usize> {
+        let done: Vec<u32> = self
+            .tasks
+            .iter()
+            .filter(|(_, task)| task.status == TaskStatus::Done)
+            .map(|(id, _)| *id)
+            .collect();
+
+        if done.is_empty() {
+            return Ok(0);
+        }
+
+        let archive_path = self.archive_path();
+        let mut archived: HashMap<u32, Task> = if Path::new(&archive_path).exists() {
+            let reader = BufReader::new(File::open(&archive_path)?);
+            serde_json::from_reader(reader)?
+        } else {
+            HashMap::new()
+        };
+
+        for id in &done {
+            if let Some(task) = self.tasks.remove(id) {
+                archived.insert(*id, task);
+            }
+        }
+
+        let json = serde_json::to_string_pretty(&archived)?;
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&archive_path)?;
+        file.write_all(json.as_bytes())?;
+
+        self.save_tasks()?;
+        Ok(done.len())
+    }
+
+    /// Read archived tasks back from the companion file, sorted by priority.
+    pub fn list_archived(&self) -> io::Result<Vec<Task>> {
+        let archive_path = self.archive_path();
+        if !Path::new(&archive_path).exists() {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(File::open(&archive_path)?);
+        let archived: HashMap<u32, Task> = serde_json::from_reader(reader)?;
+        let mut tasks: Vec<Task> = archived.into_values().collect();
+        tasks.sort_by_key(|task| task.priority);
+        Ok(tasks)
+    }
+
+    /// Re-read the backing file, replacing the in-memory task set and
+    /// re-deriving `next_id`. Used by the TUI watch mode when the file is
+    /// edited by another process. A missing file is treated as an empty set.
+    pub fn reload(&mut self) -> io::Result<()> {
+        if Path::new(&self.file_path).exists() {
+            self.load_tasks()
+        } else {
+            self.tasks.clear();
+            self.next_id = 1;
+            Ok(())
+        }
+    }
+
     fn load_tasks(&mut self) -> io::Result<()> {
         let file = File::open(&self.file_path)?;
         let reader = BufReader::new(file);
@@ -381,4 +707,213 @@ mod tests {
         assert_eq!(tasks[1].priority, TaskPriority::Medium);
         assert_eq!(tasks[2].priority, TaskPriority::High);
     }
+
+    #[test]
+    fn add_dependency_links_tasks() {
+        let mut task_manager = setup();
+
+        let a = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        let b = task_manager
+            .add_task("B".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+
+        let added = task_manager.add_dependency(b, a).unwrap();
+        let task = task_manager.tasks.get(&b).unwrap().clone();
+        delete_test_task_json();
+
+        assert!(added);
+        assert!(task.dependencies.contains(&a));
+    }
+
+    #[test]
+    fn add_dependency_rejects_cycle() {
+        let mut task_manager = setup();
+
+        let a = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        let b = task_manager
+            .add_task("B".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+
+        assert!(task_manager.add_dependency(b, a).unwrap());
+        let cycle = task_manager.add_dependency(a, b).unwrap();
+        delete_test_task_json();
+
+        assert!(!cycle);
+    }
+
+    #[test]
+    fn list_ready_tasks_excludes_blocked() {
+        let mut task_manager = setup();
+
+        let a = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        let b = task_manager
+            .add_task("B".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        task_manager.add_dependency(b, a).unwrap();
+
+        let ready: Vec<u32> = task_manager.list_ready_tasks().iter().map(|t| t.id).collect();
+        assert_eq!(ready, vec![a]);
+
+        task_manager.update_status(a, TaskStatus::Done).unwrap();
+        let ready: Vec<u32> = task_manager.list_ready_tasks().iter().map(|t| t.id).collect();
+        delete_test_task_json();
+
+        assert_eq!(ready, vec![b]);
+    }
+
+    #[test]
+    fn start_timer_moves_task_in_progress() {
+        let mut task_manager = setup();
+
+        let id = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        task_manager.start_timer(id).unwrap();
+        let status = task_manager.tasks.get(&id).unwrap().status;
+        delete_test_task_json();
+
+        assert_eq!(status, TaskStatus::InProgress);
+    }
+
+    #[test]
+    fn stop_timer_logs_entry() {
+        let mut task_manager = setup();
+
+        let id = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        task_manager.start_timer(id).unwrap();
+        let stopped = task_manager.stop_timer(id).unwrap();
+        let entries = task_manager.tasks.get(&id).unwrap().time_entries.len();
+        delete_test_task_json();
+
+        assert!(stopped);
+        assert_eq!(entries, 1);
+    }
+
+    #[test]
+    fn total_logged_sums_entries() {
+        let mut task_manager = setup();
+
+        let id = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        if let Some(task) = task_manager.tasks.get_mut(&id) {
+            task.time_entries.push(TimeEntry {
+                date: "2026-07-25".to_string(),
+                minutes: 30,
+            });
+            task.time_entries.push(TimeEntry {
+                date: "2026-07-25".to_string(),
+                minutes: 90,
+            });
+        }
+        let total = task_manager.total_logged(id);
+        delete_test_task_json();
+
+        assert_eq!(total, 120);
+        assert_eq!(split_hours_minutes(total), (2, 0));
+    }
+
+    #[test]
+    fn add_and_list_tasks_with_tag() {
+        let mut task_manager = setup();
+
+        let a = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        let b = task_manager
+            .add_task("B".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        task_manager.add_tag(a, "work").unwrap();
+        task_manager.add_tag(b, "home").unwrap();
+
+        let work: Vec<u32> = task_manager
+            .list_tasks_with_tag("work")
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        delete_test_task_json();
+
+        assert_eq!(work, vec![a]);
+    }
+
+    #[test]
+    fn remove_tag_and_all_tags() {
+        let mut task_manager = setup();
+
+        let id = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        task_manager.add_tag(id, "work").unwrap();
+        task_manager.add_tag(id, "urgent").unwrap();
+        assert!(task_manager.remove_tag(id, "work").unwrap());
+
+        let tags = task_manager.all_tags();
+        delete_test_task_json();
+
+        assert_eq!(tags, vec!["urgent".to_string()]);
+    }
+
+    #[test]
+    fn archive_done_moves_completed_tasks() {
+        let mut task_manager = setup();
+
+        let a = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        let b = task_manager
+            .add_task("B".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        task_manager.update_status(a, TaskStatus::Done).unwrap();
+
+        let archived = task_manager.archive_done().unwrap();
+        let live = task_manager.list_tasks_sorted_by_priority().len();
+        let back: Vec<u32> = task_manager
+            .list_archived()
+            .unwrap()
+            .iter()
+            .map(|t| t.id)
+            .collect();
+
+        let _ = fs::remove_file("test_tasks.archive.json");
+        delete_test_task_json();
+
+        assert_eq!(archived, 1);
+        assert_eq!(live, 1);
+        assert_eq!(back, vec![a]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn move_after_reorders_tasks() {
+        let mut task_manager = setup();
+
+        let a = task_manager
+            .add_task("A".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        let b = task_manager
+            .add_task("B".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+        let c = task_manager
+            .add_task("C".to_string(), "".to_string(), TaskPriority::Low)
+            .unwrap();
+
+        // Start order: [a, b, c]; move a after c -> [b, c, a].
+        assert!(task_manager.move_after(a, c).unwrap());
+        let order: Vec<u32> = task_manager
+            .list_tasks_ordered()
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        delete_test_task_json();
+
+        assert_eq!(order, vec![b, c, a]);
+    }
 }
@@ -1,16 +1,21 @@
-use crate::task_manager::TaskManager;
+use crate::task_manager::{split_hours_minutes, Task, TaskManager};
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use notify::{EventKind, RecursiveMode, Watcher};
 use ratatui::{prelude::*, widgets::*, Terminal};
 use std::io;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
 
 pub struct TuiApp {
     task_manager: TaskManager,
     list_state: ListState,
+    tag_filter: Option<String>,
 }
 
 impl TuiApp {
@@ -20,9 +25,31 @@ impl TuiApp {
         Self {
             task_manager,
             list_state,
+            tag_filter: None,
         }
     }
 
+    /// Tasks currently shown, honouring the active tag filter.
+    fn visible_tasks(&self) -> Vec<&Task> {
+        match &self.tag_filter {
+            Some(tag) => self.task_manager.list_tasks_with_tag(tag),
+            None => self.task_manager.list_tasks_ordered(),
+        }
+    }
+
+    /// Advance the tag filter through `None -> tag_a -> tag_b -> ... -> None`.
+    fn cycle_tag_filter(&mut self) {
+        let tags = self.task_manager.all_tags();
+        self.tag_filter = match &self.tag_filter {
+            None => tags.into_iter().next(),
+            Some(current) => {
+                let next = tags.iter().position(|t| t == current).map(|i| i + 1);
+                next.and_then(|i| tags.get(i).cloned())
+            }
+        };
+        self.list_state.select(Some(0));
+    }
+
     pub fn run(&mut self) -> io::Result<()> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
@@ -47,13 +74,83 @@ impl TuiApp {
     }
 
     fn run_app<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        // Watch the backing file so edits from another process or a second
+        // instance are reflected live instead of being lost on next save.
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(io::Error::other)?;
+        let path = self.task_manager.file_path().to_string();
+        let _ = watcher.watch(Path::new(&path), RecursiveMode::NonRecursive);
+
         loop {
             terminal.draw(|f| self.ui::<B>(f))?;
-            let tasks = self.task_manager.list_tasks_sorted_by_priority();
+            let tasks = self.visible_tasks();
+
+            // Drain pending file-change events (debounce: coalesce a burst of
+            // events into a single reload) and redraw if the file changed.
+            let mut changed = false;
+            while let Ok(event) = rx.try_recv() {
+                if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                    changed = true;
+                }
+            }
+            if changed {
+                let _ = self.task_manager.reload();
+                continue;
+            }
+
+            if !event::poll(Duration::from_millis(200))? {
+                continue;
+            }
 
             if let Event::Key(key) = event::read()? {
                 match key.code {
                     KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Char('t') => self.cycle_tag_filter(),
+                    KeyCode::Char('a') => {
+                        let _ = self.task_manager.archive_done();
+                        self.list_state.select(Some(0));
+                    }
+                    KeyCode::Char('s') => {
+                        if let Some(i) = self.list_state.selected() {
+                            if let Some(task) = tasks.get(i) {
+                                let id = task.id;
+                                let _ = self.task_manager.start_timer(id);
+                            }
+                        }
+                    }
+                    KeyCode::Char('x') => {
+                        if let Some(i) = self.list_state.selected() {
+                            if let Some(task) = tasks.get(i) {
+                                let id = task.id;
+                                let _ = self.task_manager.stop_timer(id);
+                            }
+                        }
+                    }
+                    KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                        if let Some(i) = self.list_state.selected() {
+                            if i > 0 {
+                                let id = tasks[i].id;
+                                let target = tasks[i - 1].id;
+                                let _ = self.task_manager.move_before(id, target);
+                                self.list_state.select(Some(i - 1));
+                            }
+                        }
+                    }
+                    KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                        if let Some(i) = self.list_state.selected() {
+                            if i + 1 < tasks.len() {
+                                let id = tasks[i].id;
+                                let target = tasks[i + 1].id;
+                                let _ = self.task_manager.move_after(id, target);
+                                self.list_state.select(Some(i + 1));
+                            }
+                        }
+                    }
                     KeyCode::Down => {
                         let i = match self.list_state.selected() {
                             Some(i) => {
@@ -109,18 +206,22 @@ impl TuiApp {
             ])
             .split(size);
 
-        let block = Block::default().title("Task Manager").borders(Borders::ALL);
+        let title = match &self.tag_filter {
+            Some(tag) => format!("Task Manager [tag: {}]", tag),
+            None => "Task Manager".to_string(),
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
         f.render_widget(block, size);
-        if self.task_manager.list_tasks_sorted_by_priority().is_empty() {
+
+        let visible = self.visible_tasks();
+        if visible.is_empty() {
             let no_tasks = Paragraph::new("No tasks available")
                 .block(Block::default().borders(Borders::ALL).title("Tasks"));
             f.render_widget(no_tasks, chunks[0]);
             return;
         }
 
-        let tasks: Vec<ListItem> = self
-            .task_manager
-            .list_tasks_sorted_by_priority()
+        let tasks: Vec<ListItem> = visible
             .iter()
             .map(|task| {
                 ListItem::new(Line::from(vec![Span::styled(
@@ -138,9 +239,8 @@ impl TuiApp {
         f.render_stateful_widget(tasks_list, chunks[0], &mut self.list_state.clone());
 
         if let Some(selected) = self.list_state.selected() {
-            let tasks = self.task_manager.list_tasks_sorted_by_priority();
-            if !tasks.is_empty() {
-                let task = &tasks[selected];
+            let tasks = self.visible_tasks();
+            if let Some(task) = tasks.get(selected) {
                 let task_detail = Paragraph::new(vec![
                     Line::from(vec![
                         Span::styled("Title: ", Style::default().add_modifier(Modifier::BOLD)),
@@ -161,6 +261,23 @@ impl TuiApp {
                         Span::styled("Status: ", Style::default().add_modifier(Modifier::BOLD)),
                         Span::raw(format!("{:?}", task.status)),
                     ]),
+                    Line::from(vec![
+                        Span::styled("Logged: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::raw({
+                            let (hours, minutes) =
+                                split_hours_minutes(self.task_manager.total_logged(task.id));
+                            format!("{}h {}m", hours, minutes)
+                        }),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("Tags: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::raw({
+                            let mut tags: Vec<&str> =
+                                task.tags.iter().map(String::as_str).collect();
+                            tags.sort_unstable();
+                            tags.join(", ")
+                        }),
+                    ]),
                 ])
                     .block(Block::default().borders(Borders::ALL).title("Task Details"));
 